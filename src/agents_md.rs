@@ -1,26 +1,70 @@
 use anyhow::{bail, Result};
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug)]
 pub struct AgentSection {
     pub name: String,
     pub content_lines: Vec<String>,
+    pub metadata: BTreeMap<String, String>,
 }
 
 impl AgentSection {
+    /// Parses `content`, pulling off a leading `---`-delimited frontmatter
+    /// block (simple `key: value` lines) into `metadata` and keeping the
+    /// rest as `content_lines`. Content with no frontmatter is left
+    /// untouched, so existing skills round-trip exactly as before.
     #[must_use]
     pub fn from_content(name: String, content: &str) -> Self {
+        let (metadata, content_lines) = parse_frontmatter(split_preserve_trailing_newline(content));
         Self {
             name,
-            content_lines: split_preserve_trailing_newline(content),
+            content_lines,
+            metadata,
         }
     }
 
     #[must_use]
     pub fn content_string(&self) -> String {
-        self.content_lines.join("\n")
+        render_with_frontmatter(&self.metadata, &self.content_lines).join("\n")
     }
 }
 
+fn parse_frontmatter(lines: Vec<String>) -> (BTreeMap<String, String>, Vec<String>) {
+    if lines.first().map(String::as_str) != Some("---") {
+        return (BTreeMap::new(), lines);
+    }
+    let Some(closing) = lines.iter().skip(1).position(|line| line == "---") else {
+        return (BTreeMap::new(), lines);
+    };
+    let closing = closing + 1; // account for the skip(1) above
+
+    // Every line between the delimiters must be `key: value`; otherwise this
+    // isn't frontmatter at all (e.g. a markdown horizontal rule followed by
+    // prose that happens to contain another "---"), so treat the whole
+    // thing as body content rather than silently dropping lines.
+    let mut metadata = BTreeMap::new();
+    for line in &lines[1..closing] {
+        let Some((key, value)) = line.split_once(':') else {
+            return (BTreeMap::new(), lines);
+        };
+        metadata.insert(key.trim().to_string(), value.trim().to_string());
+    }
+    (metadata, lines[closing + 1..].to_vec())
+}
+
+fn render_with_frontmatter(metadata: &BTreeMap<String, String>, content_lines: &[String]) -> Vec<String> {
+    let mut lines = Vec::new();
+    if !metadata.is_empty() {
+        lines.push("---".to_string());
+        for (key, value) in metadata {
+            lines.push(format!("{key}: {value}"));
+        }
+        lines.push("---".to_string());
+    }
+    lines.extend(content_lines.iter().cloned());
+    lines
+}
+
 #[derive(Debug)]
 pub struct AgentsDoc {
     segments: Vec<DocSegment>,
@@ -76,7 +120,12 @@ impl AgentsDoc {
                 if index >= lines.len() {
                     bail!("missing end marker for '{name}'");
                 }
-                segments.push(DocSegment::Section(AgentSection { name, content_lines }));
+                let (metadata, content_lines) = parse_frontmatter(content_lines);
+                segments.push(DocSegment::Section(AgentSection {
+                    name,
+                    content_lines,
+                    metadata,
+                }));
             } else {
                 text_lines.push(line.clone());
             }
@@ -127,6 +176,11 @@ impl AgentsDoc {
         }
     }
 
+    #[must_use]
+    pub fn get_metadata(&self, name: &str) -> Option<&BTreeMap<String, String>> {
+        self.get_section(name).map(|section| &section.metadata)
+    }
+
     pub fn remove_section(&mut self, name: &str) -> bool {
         let original_len = self.segments.len();
         self.segments.retain(|segment| match segment {
@@ -139,7 +193,7 @@ impl AgentsDoc {
     #[must_use]
     pub fn render(&self) -> String {
         let mut lines: Vec<String> = Vec::new();
-        for segment in &self.segments {
+        for segment in self.ordered_segments() {
             match segment {
                 DocSegment::Text(text_lines) => {
                     lines.extend(text_lines.clone());
@@ -148,13 +202,50 @@ impl AgentsDoc {
                     let name = &section.name;
                     lines.push(start_marker(&section.name));
                     lines.push(format!("## {name}"));
-                    lines.extend(section.content_lines.clone());
+                    lines.extend(render_with_frontmatter(&section.metadata, &section.content_lines));
                     lines.push(end_marker(&section.name));
                 }
             }
         }
         lines.join("\n")
     }
+
+    /// Segments in render order: `Text` segments keep their position, but
+    /// each maximal run of consecutive `Section` segments is stably sorted
+    /// by ascending `priority` metadata (parsed as an integer). Sections
+    /// with no parseable priority sort after those that have one, keeping
+    /// their relative order — so a doc with no priorities set renders
+    /// exactly as before.
+    fn ordered_segments(&self) -> Vec<&DocSegment> {
+        let mut ordered = Vec::with_capacity(self.segments.len());
+        let mut run: Vec<&DocSegment> = Vec::new();
+        for segment in &self.segments {
+            match segment {
+                DocSegment::Section(_) => run.push(segment),
+                DocSegment::Text(_) => {
+                    self.flush_priority_run(&mut run, &mut ordered);
+                    ordered.push(segment);
+                }
+            }
+        }
+        self.flush_priority_run(&mut run, &mut ordered);
+        ordered
+    }
+
+    fn flush_priority_run<'a>(&self, run: &mut Vec<&'a DocSegment>, ordered: &mut Vec<&'a DocSegment>) {
+        run.sort_by_key(|segment| match segment {
+            DocSegment::Section(section) => self.section_priority(&section.name),
+            DocSegment::Text(_) => i64::MAX,
+        });
+        ordered.append(run);
+    }
+
+    fn section_priority(&self, name: &str) -> i64 {
+        self.get_metadata(name)
+            .and_then(|metadata| metadata.get("priority"))
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(i64::MAX)
+    }
 }
 
 #[must_use]
@@ -167,7 +258,7 @@ pub fn render_sections(sections: &[AgentSection]) -> String {
         let name = &section.name;
         lines.push(start_marker(name));
         lines.push(format!("## {name}"));
-        lines.extend(section.content_lines.clone());
+        lines.extend(render_with_frontmatter(&section.metadata, &section.content_lines));
         lines.push(end_marker(name));
     }
     lines.join("\n")
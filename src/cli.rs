@@ -0,0 +1,121 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(name = "prime-agent", version, about = "Sync reusable skills into AGENTS.md")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    /// Directory skills are stored in, overriding config and env.
+    #[arg(long, global = true)]
+    pub skills_dir: Option<PathBuf>,
+
+    /// Path to the AGENTS.md file to read/write.
+    #[arg(long, global = true)]
+    pub agents_path: Option<PathBuf>,
+
+    /// Override a config value for this invocation (key:value, repeatable).
+    #[arg(long = "config", value_name = "KEY:VALUE", global = true)]
+    pub config_overrides: Vec<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Load one or more skills and render them into AGENTS.md.
+    Get {
+        /// Skill names to load (comma-separated values are also expanded).
+        skills: Vec<String>,
+    },
+    /// Save a skill's content from a file into the skills store.
+    Set {
+        name: String,
+        path: PathBuf,
+    },
+    /// Reconcile the skills store with AGENTS.md.
+    Sync {
+        /// How to resolve hunks where both sides changed the same region.
+        #[arg(long, value_enum, default_value_t = MergeStrategyArg::Interactive)]
+        strategy: MergeStrategyArg,
+        /// Show what would change without writing any files.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List all known skill names.
+    List,
+    /// Inspect or update the config file.
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+    /// Remove a skill's section from AGENTS.md, leaving the skill itself.
+    Delete {
+        name: String,
+    },
+    /// Remove a skill's section from AGENTS.md and delete it from the store.
+    DeleteGlobally {
+        name: String,
+    },
+    /// List recent sync/save operations recorded in the journal.
+    History {
+        /// Max number of entries to show, most recent first.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Restore the most recent pre-image for one skill, or every skill.
+    Undo {
+        /// Restore only this skill; omit to undo every journaled skill.
+        #[arg(long)]
+        skill: Option<String>,
+    },
+    /// Seed a fresh config and starter skill library for a new install.
+    Init,
+}
+
+/// CLI-facing mirror of `sync::MergeStrategy`, kept separate so clap's
+/// `ValueEnum` derive doesn't leak into the sync module's public API.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum MergeStrategyArg {
+    Interactive,
+    PreferSkill,
+    PreferAgents,
+    Union,
+    FailOnConflict,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    Set { name: String, value: String },
+    Get { name: String },
+}
+
+/// Kebab-case names of every built-in `Command` variant, used to keep a
+/// user-defined `alias.<name>` from shadowing a real subcommand.
+pub const COMMAND_NAMES: &[&str] = &[
+    "get",
+    "set",
+    "sync",
+    "list",
+    "config",
+    "delete",
+    "delete-globally",
+    "history",
+    "undo",
+    "init",
+];
+
+/// Expands raw skill arguments into individual skill names, splitting each
+/// argument on commas so both `get a b c` and `get a,b,c` work.
+#[must_use]
+pub fn expand_skill_args(skills: Vec<String>) -> Vec<String> {
+    let mut expanded = Vec::with_capacity(skills.len());
+    for skill in skills {
+        for part in skill.split(',') {
+            let trimmed = part.trim();
+            if !trimmed.is_empty() {
+                expanded.push(trimmed.to_string());
+            }
+        }
+    }
+    expanded
+}
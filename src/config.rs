@@ -11,6 +11,15 @@ pub struct Config {
     skills_dir: Option<PathBuf>,
     #[serde(flatten)]
     values: HashMap<String, String>,
+    /// Which layer (a file path, or "env") last set each key. Not
+    /// persisted; only meaningful on a `Config` built by `load_layered`.
+    #[serde(skip)]
+    provenance: BTreeMap<String, String>,
+    /// Every file that contributed at least one value, in the order
+    /// `load_layered` merged them (lowest precedence first). Not
+    /// persisted; only meaningful on a `Config` built by `load_layered`.
+    #[serde(skip)]
+    sources: Vec<PathBuf>,
 }
 
 impl Config {
@@ -37,6 +46,99 @@ impl Config {
         }
     }
 
+    /// Deep-merges `paths` in precedence order (later paths win key-by-key
+    /// over earlier ones), then layers `PRIME_AGENT_*` environment
+    /// variables on top of all of them. Missing paths are skipped rather
+    /// than erroring, so callers can pass a user config alongside any
+    /// project-local configs discovered via `discover_config_paths`.
+    /// `.toml` paths are read with the flat `key = "value"` parser;
+    /// everything else is read as JSON.
+    pub fn load_layered(paths: &[PathBuf]) -> Result<Self> {
+        let mut config = Self::default();
+        for path in paths {
+            if !path.exists() {
+                continue;
+            }
+            let layer = Self::load_layer(path)?;
+            config.merge_from(&layer, path);
+        }
+        config.apply_env_overrides();
+        Ok(config)
+    }
+
+    fn load_layer(path: &Path) -> Result<Self> {
+        if path.extension().and_then(|ext| ext.to_str()) == Some("toml") {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("failed to read config '{}'", path.display()))?;
+            Ok(Self::parse_flat(&contents))
+        } else {
+            Self::load_from_path(path)
+        }
+    }
+
+    /// Parses the Cargo-style `key = "value"` lines used by a
+    /// project-local `.prime-agent.toml`. No `[section]` headers; every
+    /// line is a top-level config key, same as the JSON file's flat map.
+    fn parse_flat(contents: &str) -> Self {
+        let mut config = Self::default();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"');
+            config.set_value(key.trim(), value);
+        }
+        config
+    }
+
+    fn merge_from(&mut self, other: &Self, source: &Path) {
+        let source_label = source.display().to_string();
+        if let Some(skills_dir) = &other.skills_dir {
+            self.skills_dir = Some(skills_dir.clone());
+            self.provenance.insert("skills-dir".to_string(), source_label.clone());
+        }
+        for (key, value) in &other.values {
+            self.values.insert(key.clone(), value.clone());
+            self.provenance.insert(key.clone(), source_label.clone());
+        }
+        if other.skills_dir.is_some() || !other.values.is_empty() {
+            self.sources.push(source.to_path_buf());
+        }
+    }
+
+    /// Every file that contributed at least one value during
+    /// `load_layered`, in merge order (lowest precedence first).
+    #[must_use]
+    pub fn sources(&self) -> &[PathBuf] {
+        &self.sources
+    }
+
+    /// Overrides with `PRIME_AGENT_SKILLS_DIR` and `PRIME_AGENT_<KEY>`
+    /// environment variables, the highest-precedence layer.
+    fn apply_env_overrides(&mut self) {
+        for (raw_key, value) in env::vars() {
+            let Some(suffix) = raw_key.strip_prefix("PRIME_AGENT_") else {
+                continue;
+            };
+            if suffix.is_empty() {
+                continue;
+            }
+            let key = suffix.to_lowercase().replace('_', "-");
+            self.set_value(&key, &value);
+            self.provenance.insert(key, "env".to_string());
+        }
+    }
+
+    /// The layer (file path, or `"env"`) that last supplied `key`'s value,
+    /// if it was set through `load_layered`.
+    pub fn provenance(&self, key: &str) -> Option<&str> {
+        self.provenance.get(key).map(String::as_str)
+    }
+
     pub fn save_to_path(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
@@ -63,6 +165,12 @@ impl Config {
         }
     }
 
+    /// Looks up a user-defined command shortcut from an `alias.<name>`
+    /// config key (e.g. `alias.bootstrap = "get rust testing git"`).
+    pub fn alias(&self, name: &str) -> Option<String> {
+        self.get_value(&format!("alias.{name}"))
+    }
+
     pub fn get_value(&self, name: &str) -> Option<String> {
         if name == "skills-dir" {
             return self.skills_dir.as_ref().map(|path| path.display().to_string());
@@ -81,9 +189,13 @@ impl Config {
         values
     }
 
+    /// Applies explicit `--config key:value` overrides, the highest link
+    /// in the precedence chain (file < env < `--config`). Called after
+    /// `load_layered` so these win over both files and `PRIME_AGENT_*`.
     pub fn apply_overrides(&mut self, overrides: &HashMap<String, String>) {
         for (key, value) in overrides {
             self.set_value(key, value);
+            self.provenance.insert(key.clone(), "--config".to_string());
         }
     }
 }
@@ -96,24 +208,65 @@ pub fn ensure_config_file(path: &Path) -> Result<()> {
 }
 
 pub fn config_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("config"))
+}
+
+/// Where `init` points a freshly-written config's `skills-dir` at: a
+/// `skills` subdirectory alongside the config file itself.
+pub fn default_skills_dir() -> Result<PathBuf> {
+    Ok(config_dir()?.join("skills"))
+}
+
+/// All config files that apply to the current directory, in precedence
+/// order (later entries override earlier ones in `Config::load_layered`):
+/// the user config first, then every `.prime-agent/config` and
+/// `.prime-agent.toml` found walking from the filesystem root down to the
+/// CWD, so the directory closest to the CWD wins.
+pub fn discover_config_paths() -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    let user_path = config_path()?;
+    if user_path.exists() {
+        paths.push(user_path);
+    }
+
+    let cwd = env::current_dir().context("failed to read current directory")?;
+    let mut ancestors: Vec<PathBuf> = cwd.ancestors().map(Path::to_path_buf).collect();
+    ancestors.reverse(); // filesystem root first, CWD last
+
+    for dir in ancestors {
+        let toml_path = dir.join(".prime-agent.toml");
+        if toml_path.exists() {
+            paths.push(toml_path);
+        }
+        let dir_config_path = dir.join(".prime-agent").join("config");
+        if dir_config_path.exists() {
+            paths.push(dir_config_path);
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Path to the append-only sync journal, alongside the config file.
+pub fn journal_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("journal.jsonl"))
+}
+
+fn config_dir() -> Result<PathBuf> {
     if cfg!(target_os = "windows") {
         bail!("Microslop skill issues");
     }
     if let Ok(base) = env::var("XDG_CONFIG_HOME") {
-        return Ok(PathBuf::from(base).join("prime-agent").join("config"));
+        return Ok(PathBuf::from(base).join("prime-agent"));
     }
     if let Ok(home) = env::var("HOME") {
         if cfg!(target_os = "macos") {
             return Ok(PathBuf::from(home)
                 .join("Library")
                 .join("Application Support")
-                .join("prime-agent")
-                .join("config"));
+                .join("prime-agent"));
         }
-        return Ok(PathBuf::from(home)
-            .join(".config")
-            .join("prime-agent")
-            .join("config"));
+        return Ok(PathBuf::from(home).join(".config").join("prime-agent"));
     }
     bail!("HOME not set and XDG_CONFIG_HOME not set");
 }
@@ -0,0 +1,67 @@
+use crate::config::{self, Config};
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use std::fs;
+use std::path::Path;
+use tar::Archive;
+
+/// Curated starter skills, baked into the binary so `init` works offline
+/// and doesn't depend on a registry being reachable on a fresh install.
+static STARTER_SKILLS_ARCHIVE: &[u8] = include_bytes!("../assets/starter-skills.tar.gz");
+
+/// Seeds a new install: writes a default config (if one isn't already
+/// there) pointing `skills-dir` at `config::default_skills_dir`, then
+/// unpacks the embedded starter skills into the resolved skills dir.
+///
+/// Idempotent like `ensure_config_file`: an existing config is left
+/// alone, and only starter skills missing from the skills dir are
+/// restored, so re-running `init` never clobbers a user's edits.
+pub fn run_init() -> Result<()> {
+    let config_path = config::config_path()?;
+    let skills_dir = if config_path.exists() {
+        Config::load_required(&config_path)?
+            .skills_dir()
+            .unwrap_or(config::default_skills_dir()?)
+    } else {
+        let default_dir = config::default_skills_dir()?;
+        let mut config = Config::default();
+        config.set_value("skills-dir", &default_dir.display().to_string());
+        config.save_to_path(&config_path)?;
+        println!("wrote default config to '{}'", config_path.display());
+        default_dir
+    };
+
+    install_starter_skills(&skills_dir)
+}
+
+/// Extracts every starter skill from the embedded archive into
+/// `skills_dir`, skipping any file that's already present so a second run
+/// only fills gaps instead of overwriting what's there.
+fn install_starter_skills(skills_dir: &Path) -> Result<()> {
+    let decoder = GzDecoder::new(STARTER_SKILLS_ARCHIVE);
+    let mut archive = Archive::new(decoder);
+    for entry in archive
+        .entries()
+        .context("failed to read starter skill archive")?
+    {
+        let mut entry = entry.context("failed to read starter skill archive entry")?;
+        let relative_path = entry
+            .path()
+            .context("invalid path in starter skill archive")?
+            .into_owned();
+        let target = skills_dir.join(&relative_path);
+        if target.exists() {
+            println!("skip {} (already present)", relative_path.display());
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create '{}'", parent.display()))?;
+        }
+        entry
+            .unpack(&target)
+            .with_context(|| format!("failed to install '{}'", target.display()))?;
+        println!("installed {}", relative_path.display());
+    }
+    Ok(())
+}
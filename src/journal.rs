@@ -0,0 +1,145 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default number of entries kept once a journal grows past it; older
+/// entries are dropped on the next compaction, oldest first.
+pub const DEFAULT_RETENTION: usize = 500;
+
+/// What a journal entry's pre-image was captured ahead of.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// `prime-agent set` or a sync writing a new SKILL.md over an old one.
+    SaveSkill,
+    /// A sync-resolved AGENTS.md write.
+    SyncAgents,
+}
+
+/// One append-only record: the content a write was about to overwrite,
+/// captured before the write happened.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct JournalEntry {
+    pub timestamp: u64,
+    pub skill: String,
+    pub operation: Operation,
+    pub content_hash: String,
+    pub pre_image: String,
+}
+
+/// Append-only log of pre-images, stored as one JSON object per line under
+/// the config directory, bounded by compacting down to `retention` entries
+/// whenever a write pushes it over.
+pub struct JournalStore {
+    path: PathBuf,
+    retention: usize,
+}
+
+impl JournalStore {
+    #[must_use]
+    pub fn new(path: PathBuf, retention: usize) -> Self {
+        Self { path, retention }
+    }
+
+    /// Appends a pre-image record for `skill`, then compacts the log back
+    /// down to `retention` entries if it has grown past that.
+    pub fn record(&self, skill: &str, operation: Operation, pre_image: &str) -> Result<()> {
+        let entry = JournalEntry {
+            timestamp: now_unix(),
+            skill: skill.to_string(),
+            operation,
+            content_hash: content_hash(pre_image),
+            pre_image: pre_image.to_string(),
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create journal dir '{}'", parent.display()))?;
+        }
+        let line = serde_json::to_string(&entry).context("failed to serialize journal entry")?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("failed to open journal '{}'", self.path.display()))?;
+        writeln!(file, "{line}")
+            .with_context(|| format!("failed to append to journal '{}'", self.path.display()))?;
+        self.compact()
+    }
+
+    /// All entries, oldest first.
+    pub fn all(&self) -> Result<Vec<JournalEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("failed to read journal '{}'", self.path.display()))?;
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line)
+                    .with_context(|| format!("failed to parse journal line: {line}"))
+            })
+            .collect()
+    }
+
+    /// Most recent entries first, capped at `limit`.
+    pub fn recent(&self, limit: usize) -> Result<Vec<JournalEntry>> {
+        let mut entries = self.all()?;
+        entries.reverse();
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    /// The most recently journaled pre-image for `skill`, if any.
+    pub fn latest_pre_image(&self, skill: &str) -> Result<Option<JournalEntry>> {
+        Ok(self.all()?.into_iter().rev().find(|entry| entry.skill == skill))
+    }
+
+    /// Distinct skill names with at least one journaled pre-image, sorted.
+    pub fn known_skills(&self) -> Result<Vec<String>> {
+        let mut names: Vec<String> = self
+            .all()?
+            .into_iter()
+            .filter(|entry| entry.operation == Operation::SaveSkill)
+            .map(|entry| entry.skill)
+            .collect();
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    fn compact(&self) -> Result<()> {
+        let entries = self.all()?;
+        if entries.len() <= self.retention {
+            return Ok(());
+        }
+        let kept = &entries[entries.len() - self.retention..];
+        let mut rewritten = String::new();
+        for entry in kept {
+            rewritten.push_str(&serde_json::to_string(entry).context("failed to serialize journal entry")?);
+            rewritten.push('\n');
+        }
+        fs::write(&self.path, rewritten)
+            .with_context(|| format!("failed to compact journal '{}'", self.path.display()))?;
+        Ok(())
+    }
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn content_hash(content: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
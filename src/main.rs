@@ -9,16 +9,27 @@ use std::path::{Path, PathBuf};
 mod agents_md;
 mod cli;
 mod config;
+mod init;
+mod journal;
+mod skill_vars;
 mod skills_store;
 mod sync;
 
 use crate::agents_md::AgentSection;
-use crate::cli::{Cli, Command, ConfigAction};
+use crate::cli::{Cli, Command, ConfigAction, MergeStrategyArg};
 use crate::config::Config;
+use crate::journal::{JournalStore, DEFAULT_RETENTION};
 use crate::skills_store::SkillsStore;
+use crate::sync::MergeStrategy;
+
+/// Cap on how many times an alias can expand into another alias before
+/// `expand_aliases` gives up, so a cycle (`alias.a = "b"`, `alias.b = "a"`)
+/// fails fast instead of splicing forever.
+const MAX_ALIAS_DEPTH: usize = 8;
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let args = expand_aliases(std::env::args().collect())?;
+    let cli = Cli::parse_from(args);
     let version = env!("CARGO_PKG_VERSION");
     println!("\u{001b}[32mprime-agent({version})\u{001b}[0m");
 
@@ -28,20 +39,32 @@ fn main() -> Result<()> {
         handle_config_command(action.as_ref())?;
         return Ok(());
     }
+    if let Command::Init = &cli.command {
+        init::run_init()?;
+        return Ok(());
+    }
 
     let skills_dir = resolve_skills_dir(&cli, &overrides)?;
     let agents_path = cli
         .agents_path
         .unwrap_or_else(|| PathBuf::from("AGENTS.md"));
-    let skills_store = SkillsStore::new(skills_dir);
+    let journal_retention = overrides
+        .get("journal-retention")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_RETENTION);
+    let journal = JournalStore::new(config::journal_path()?, journal_retention);
+    let skills_store = SkillsStore::new(skills_dir, journal);
 
     match cli.command {
         Command::Get { skills } => {
-            let skill_names = cli::expand_skill_args(skills)?;
+            let skill_names = cli::expand_skill_args(skills);
             let mut sections = Vec::with_capacity(skill_names.len());
             for name in skill_names {
                 SkillsStore::validate_name(&name)?;
+                let name = skills_store.resolve_one(&name)?;
                 let content = skills_store.load_skill(&name)?;
+                let vars_path = skills_store.vars_path(&name);
+                let content = skill_vars::resolve_template(&content, &vars_path, &overrides)?;
                 sections.push(AgentSection::from_content(name, &content));
             }
             let rendered = agents_md::render_sections(&sections);
@@ -52,8 +75,8 @@ fn main() -> Result<()> {
             let content = std::fs::read_to_string(&path)?;
             skills_store.save_skill(&name, &content)?;
         }
-        Command::Sync => {
-            sync::run_sync(&skills_store, &agents_path)?;
+        Command::Sync { strategy, dry_run } => {
+            sync::run_sync(&skills_store, &agents_path, strategy.into(), dry_run, &overrides)?;
         }
         Command::List => {
             for name in skills_store.list_skill_names()? {
@@ -63,6 +86,9 @@ fn main() -> Result<()> {
         Command::Config { .. } => {
             unreachable!("config command handled before skills setup");
         }
+        Command::Init => {
+            unreachable!("init command handled before skills setup");
+        }
         Command::Delete { name } => {
             SkillsStore::validate_name(&name)?;
             let contents = std::fs::read_to_string(&agents_path)
@@ -75,6 +101,7 @@ fn main() -> Result<()> {
         }
         Command::DeleteGlobally { name } => {
             SkillsStore::validate_name(&name)?;
+            let name = skills_store.resolve_one(&name)?;
             let contents = std::fs::read_to_string(&agents_path)
                 .with_context(|| format!("failed to read '{}'", agents_path.display()))?;
             let mut doc = agents_md::AgentsDoc::parse(&contents)?;
@@ -84,22 +111,118 @@ fn main() -> Result<()> {
             }
             skills_store.delete_skill(&name)?;
         }
+        Command::History { limit } => {
+            for entry in skills_store.journal().recent(limit)? {
+                println!(
+                    "{}\t{}\t{:?}\t{}",
+                    entry.timestamp, entry.skill, entry.operation, entry.content_hash
+                );
+            }
+        }
+        Command::Undo { skill } => {
+            let restored = sync::run_undo(&skills_store, &agents_path, skill.as_deref(), &overrides)?;
+            if restored.is_empty() {
+                println!("Nothing to undo.");
+            } else {
+                for name in restored {
+                    println!("restored {name}");
+                }
+            }
+        }
     }
     Ok(())
 }
 
+impl From<MergeStrategyArg> for MergeStrategy {
+    fn from(arg: MergeStrategyArg) -> Self {
+        match arg {
+            MergeStrategyArg::Interactive => Self::Interactive,
+            MergeStrategyArg::PreferSkill => Self::PreferSkill,
+            MergeStrategyArg::PreferAgents => Self::PreferAgents,
+            MergeStrategyArg::Union => Self::Union,
+            MergeStrategyArg::FailOnConflict => Self::FailOnConflict,
+        }
+    }
+}
+
+/// Global long flags that consume the following argument as their value, so
+/// that value is never mistaken for the command/alias token below. A flag
+/// given in `--flag=value` form is a single token and isn't affected.
+const VALUE_FLAGS: &[&str] = &["--skills-dir", "--agents-path", "--config"];
+
+/// Index of the first argument that's a command/alias candidate: not a flag
+/// itself, and not the value slot owned by a preceding `VALUE_FLAGS` entry.
+fn find_command_token_index(args: &[String]) -> Option<usize> {
+    let mut index = 1;
+    while index < args.len() {
+        if VALUE_FLAGS.contains(&args[index].as_str()) {
+            index += 2; // skip the flag and the value it consumes
+            continue;
+        }
+        if args[index].starts_with('-') {
+            index += 1;
+            continue;
+        }
+        return Some(index);
+    }
+    None
+}
+
+/// Splices a user-defined `alias.<name>` config value in for `name` before
+/// clap ever sees it, so e.g. `alias.bootstrap = "get rust testing git"`
+/// lets users run `prime-agent bootstrap`. Only the first non-flag
+/// argument is treated as the candidate command name; known `Command`
+/// variants are left untouched so an alias can never shadow a built-in.
+fn expand_aliases(mut args: Vec<String>) -> Result<Vec<String>> {
+    let mut depth = 0;
+    loop {
+        let Some(index) = find_command_token_index(&args) else {
+            return Ok(args);
+        };
+        let name = args[index].clone();
+        if cli::COMMAND_NAMES.contains(&name.as_str()) {
+            return Ok(args);
+        }
+
+        let config = Config::load_layered(&config::discover_config_paths()?)?;
+        let Some(alias) = config.alias(&name) else {
+            return Ok(args);
+        };
+        depth += 1;
+        if depth > MAX_ALIAS_DEPTH {
+            return Err(anyhow!(
+                "alias '{name}' did not resolve after {MAX_ALIAS_DEPTH} expansions; check for an alias cycle"
+            ));
+        }
+
+        let expansion: Vec<String> = alias.split_whitespace().map(str::to_string).collect();
+        if expansion.is_empty() {
+            return Err(anyhow!("alias '{name}' expands to nothing"));
+        }
+        args.splice(index..=index, expansion);
+    }
+}
+
 fn handle_config_command(action: Option<&ConfigAction>) -> Result<()> {
     let path = config::config_path()?;
     config::ensure_config_file(&path)?;
     match action {
         Some(ConfigAction::Set { name, value }) => {
+            if let Some(alias_name) = name.strip_prefix("alias.")
+                && cli::COMMAND_NAMES.contains(&alias_name)
+            {
+                return Err(anyhow!(
+                    "alias '{alias_name}' would shadow the built-in '{alias_name}' command"
+                ));
+            }
             let mut config = Config::load_or_default(&path)?;
             config.set_value(name, value);
             config.save_to_path(&path)?;
-            print_config_with_updated(&config, name);
+            let layered = Config::load_layered(&config::discover_config_paths()?)?;
+            print_config_with_updated(&layered, name);
         }
         Some(ConfigAction::Get { name }) => {
-            let config = Config::load_required(&path)?;
+            let config = Config::load_layered(&config::discover_config_paths()?)?;
             if let Some(value) = config.get_value(name) {
                 println!("{value}");
             } else {
@@ -107,7 +230,7 @@ fn handle_config_command(action: Option<&ConfigAction>) -> Result<()> {
             }
         }
         None => {
-            let config = Config::load_required(&path)?;
+            let config = Config::load_layered(&config::discover_config_paths()?)?;
             print_config(&config);
         }
     }
@@ -124,15 +247,7 @@ fn resolve_skills_dir(
     if let Some(path) = cli.skills_dir.clone() {
         return Ok(expand_path(&path));
     }
-    if let Ok(env_path) = env::var("PRIME_AGENT_SKILLS_DIR") {
-        return Ok(expand_path(Path::new(&env_path)));
-    }
-    let config_path = config::config_path()?;
-    let mut config = if config_path.exists() {
-        Config::load_required(&config_path)?
-    } else {
-        Config::default()
-    };
+    let mut config = Config::load_layered(&config::discover_config_paths()?)?;
     config.apply_overrides(overrides);
     config
         .skills_dir()
@@ -166,13 +281,27 @@ fn print_config(config: &Config) {
     let skills_dir = values
         .get("skills-dir")
         .map_or_else(|| "<missing>".to_string(), Clone::clone);
-    println!("skills-dir={skills_dir}");
+    println!("skills-dir={skills_dir}{}", provenance_suffix(config, "skills-dir"));
     println!("Optional:");
     for (key, value) in values {
         if key == "skills-dir" {
             continue;
         }
-        println!("{key}={value}");
+        println!("{key}={value}{}", provenance_suffix(config, &key));
+    }
+    if !config.sources().is_empty() {
+        println!("Sources (lowest precedence first):");
+        for source in config.sources() {
+            println!("  {}", source.display());
+        }
+    }
+}
+
+fn provenance_suffix(config: &Config, key: &str) -> String {
+    match config.provenance(key) {
+        Some("env") => " (overridden by environment)".to_string(),
+        Some(source) => format!(" (from {source})"),
+        None => String::new(),
     }
 }
 
@@ -185,7 +314,7 @@ fn print_config_with_updated(config: &Config, updated_key: &str) {
     if updated_key == "skills-dir" {
         println!("skills-dir={skills_dir} (updated)");
     } else {
-        println!("skills-dir={skills_dir}");
+        println!("skills-dir={skills_dir}{}", provenance_suffix(config, "skills-dir"));
     }
     println!("Optional:");
     for (key, value) in values {
@@ -195,7 +324,7 @@ fn print_config_with_updated(config: &Config, updated_key: &str) {
         if key == updated_key {
             println!("{key}={value} (updated)");
         } else {
-            println!("{key}={value}");
+            println!("{key}={value}{}", provenance_suffix(config, &key));
         }
     }
 }
@@ -0,0 +1,308 @@
+use anyhow::{bail, Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{IsTerminal, Write as _};
+use std::path::Path;
+
+/// A `{{name}}` placeholder declared in a skill's `<name>.vars.toml`
+/// sidecar, alongside whatever the user has previously supplied for it.
+#[derive(Debug, Clone, Default)]
+struct VarDecl {
+    description: Option<String>,
+    default: Option<String>,
+    value: Option<String>,
+}
+
+/// The `<name>.vars.toml` sidecar for one skill: one `[var]` table per
+/// declared placeholder, with `description`, `default`, and the
+/// previously-entered `value` persisted from an earlier interactive run.
+#[derive(Debug, Default)]
+struct VarsSidecar {
+    vars: BTreeMap<String, VarDecl>,
+}
+
+impl VarsSidecar {
+    fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read skill vars '{}'", path.display()))?;
+        Ok(Self::parse(&contents))
+    }
+
+    fn parse(contents: &str) -> Self {
+        let mut vars: BTreeMap<String, VarDecl> = BTreeMap::new();
+        let mut current: Option<String> = None;
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                let name = line[1..line.len() - 1].trim().to_string();
+                vars.entry(name.clone()).or_default();
+                current = Some(name);
+                continue;
+            }
+            let Some(name) = &current else { continue };
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = unquote(value.trim());
+            let decl = vars.entry(name.clone()).or_default();
+            match key.trim() {
+                "description" => decl.description = Some(value),
+                "default" => decl.default = Some(value),
+                "value" => decl.value = Some(value),
+                _ => {}
+            }
+        }
+        Self { vars }
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create '{}'", parent.display()))?;
+        }
+        let mut out = String::new();
+        for (name, decl) in &self.vars {
+            let _ = writeln!(out, "[{name}]");
+            if let Some(description) = &decl.description {
+                let _ = writeln!(out, "description = \"{description}\"");
+            }
+            if let Some(default) = &decl.default {
+                let _ = writeln!(out, "default = \"{default}\"");
+            }
+            if let Some(value) = &decl.value {
+                let _ = writeln!(out, "value = \"{value}\"");
+            }
+            out.push('\n');
+        }
+        fs::write(path, out)
+            .with_context(|| format!("failed to write skill vars '{}'", path.display()))?;
+        Ok(())
+    }
+}
+
+fn unquote(value: &str) -> String {
+    value.trim_matches('"').to_string()
+}
+
+/// Resolves every `{{placeholder}}` token in `content` and substitutes it,
+/// reading declarations from the sidecar at `sidecar_path`.
+///
+/// Resolution order per token: `overrides` (typically `--config
+/// key:value`), then `PRIME_AGENT_VAR_<NAME>`, then the sidecar's
+/// persisted `value`, then its declared `default`. If none apply and the
+/// token is declared but still unresolved, an interactive terminal is
+/// prompted and the answer is written back into the sidecar. Tokens with
+/// no `[name]` declaration at all, and declared-but-unresolved tokens with
+/// no terminal to prompt on, are collected and reported together as a
+/// single error.
+pub fn resolve_template(
+    content: &str,
+    sidecar_path: &Path,
+    overrides: &HashMap<String, String>,
+) -> Result<String> {
+    let tokens = extract_tokens(content);
+    if tokens.is_empty() {
+        return Ok(content.to_string());
+    }
+
+    let mut sidecar = VarsSidecar::load(sidecar_path)?;
+    let mut resolved = BTreeMap::new();
+    let mut missing = Vec::new();
+    let mut sidecar_dirty = false;
+
+    for name in &tokens {
+        if let Some(value) = overrides.get(name) {
+            resolved.insert(name.clone(), value.clone());
+            continue;
+        }
+        let env_key = format!("PRIME_AGENT_VAR_{}", name.to_uppercase().replace('-', "_"));
+        if let Ok(value) = env::var(env_key) {
+            resolved.insert(name.clone(), value);
+            continue;
+        }
+        let Some(decl) = sidecar.vars.get(name) else {
+            missing.push(name.clone());
+            continue;
+        };
+        if let Some(value) = &decl.value {
+            resolved.insert(name.clone(), value.clone());
+            continue;
+        }
+        if let Some(default) = &decl.default {
+            resolved.insert(name.clone(), default.clone());
+            continue;
+        }
+        if std::io::stdin().is_terminal() {
+            let value = prompt_for(name, decl)?;
+            sidecar.vars.entry(name.clone()).or_default().value = Some(value.clone());
+            sidecar_dirty = true;
+            resolved.insert(name.clone(), value);
+        } else {
+            missing.push(name.clone());
+        }
+    }
+
+    if !missing.is_empty() {
+        bail!(
+            "skill references undeclared template variable(s): {}",
+            missing.join(", ")
+        );
+    }
+
+    if sidecar_dirty {
+        sidecar.save(sidecar_path)?;
+    }
+
+    Ok(substitute(content, &resolved))
+}
+
+fn prompt_for(name: &str, decl: &VarDecl) -> Result<String> {
+    match &decl.description {
+        Some(description) => println!("{name} ({description}): "),
+        None => println!("{name}: "),
+    }
+    print!("> ");
+    std::io::stdout()
+        .flush()
+        .context("failed to flush stdout")?;
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .context("failed to read template variable from stdin")?;
+    Ok(input.trim().to_string())
+}
+
+/// Distinct `{{...}}` token names found in `content`, trimmed and
+/// de-duplicated, in first-seen order.
+fn extract_tokens(content: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim().to_string();
+        if !name.is_empty() && !tokens.contains(&name) {
+            tokens.push(name);
+        }
+        rest = &after[end + 2..];
+    }
+    tokens
+}
+
+fn substitute(content: &str, resolved: &BTreeMap<String, String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            output.push_str("{{");
+            rest = after;
+            break;
+        };
+        let name = after[..end].trim();
+        match resolved.get(name) {
+            Some(value) => output.push_str(value),
+            None => {
+                let _ = write!(output, "{{{{{name}}}}}");
+            }
+        }
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn unique_sidecar_path() -> std::path::PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!(
+            "prime-agent-skill-vars-test-{}-{id}.vars.toml",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn content_with_no_tokens_round_trips_unchanged() {
+        let path = unique_sidecar_path();
+        let result = resolve_template("no tokens here", &path, &HashMap::new()).unwrap();
+        assert_eq!(result, "no tokens here");
+    }
+
+    #[test]
+    fn override_takes_precedence_over_declared_default() {
+        let path = unique_sidecar_path();
+        fs::write(&path, "[project_name]\ndefault = \"Fallback\"\n").unwrap();
+        let mut overrides = HashMap::new();
+        overrides.insert("project_name".to_string(), "FromOverride".to_string());
+        let result = resolve_template("Hello {{project_name}}.", &path, &overrides).unwrap();
+        assert_eq!(result, "Hello FromOverride.");
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn env_var_takes_precedence_over_persisted_value_and_default() {
+        let path = unique_sidecar_path();
+        fs::write(
+            &path,
+            "[project_name]\ndefault = \"Fallback\"\nvalue = \"Persisted\"\n",
+        )
+        .unwrap();
+        let env_key = "PRIME_AGENT_VAR_PROJECT_NAME";
+        // SAFETY: this test owns `env_key` for its duration and restores it
+        // afterward; no other test reads or writes the same key.
+        unsafe {
+            env::set_var(env_key, "FromEnv");
+        }
+        let result = resolve_template("Hello {{project_name}}.", &path, &HashMap::new());
+        unsafe {
+            env::remove_var(env_key);
+        }
+        assert_eq!(result.unwrap(), "Hello FromEnv.");
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn persisted_value_takes_precedence_over_default() {
+        let path = unique_sidecar_path();
+        fs::write(
+            &path,
+            "[project_name]\ndefault = \"Fallback\"\nvalue = \"Persisted\"\n",
+        )
+        .unwrap();
+        let result = resolve_template("Hello {{project_name}}.", &path, &HashMap::new()).unwrap();
+        assert_eq!(result, "Hello Persisted.");
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn falls_back_to_default_when_nothing_else_resolves_it() {
+        let path = unique_sidecar_path();
+        fs::write(&path, "[project_name]\ndefault = \"Fallback\"\n").unwrap();
+        let result = resolve_template("Hello {{project_name}}.", &path, &HashMap::new()).unwrap();
+        assert_eq!(result, "Hello Fallback.");
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn undeclared_token_with_no_terminal_to_prompt_errors() {
+        let path = unique_sidecar_path();
+        let err = resolve_template("Hello {{project_name}}.", &path, &HashMap::new()).unwrap_err();
+        assert!(err.to_string().contains("project_name"));
+    }
+}
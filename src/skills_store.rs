@@ -1,16 +1,22 @@
+use crate::journal::{JournalStore, Operation};
 use anyhow::{bail, Context, Result};
 use std::fs;
 use std::path::PathBuf;
 
 pub struct SkillsStore {
     root: PathBuf,
+    journal: JournalStore,
 }
 
 impl SkillsStore {
     #[must_use]
-    #[allow(clippy::missing_const_for_fn)]
-    pub fn new(root: PathBuf) -> Self {
-        Self { root }
+    pub fn new(root: PathBuf, journal: JournalStore) -> Self {
+        Self { root, journal }
+    }
+
+    #[must_use]
+    pub fn journal(&self) -> &JournalStore {
+        &self.journal
     }
 
     pub fn validate_name(name: &str) -> Result<()> {
@@ -31,6 +37,43 @@ impl SkillsStore {
         self.root.join(name).join("SKILL.md")
     }
 
+    #[must_use]
+    pub fn base_path(&self, name: &str) -> PathBuf {
+        self.root.join(name).join(".base.md")
+    }
+
+    /// Path to `name`'s template-variable sidecar, declaring its
+    /// `{{placeholder}}` tokens for `skill_vars::resolve_template`.
+    #[must_use]
+    pub fn vars_path(&self, name: &str) -> PathBuf {
+        self.root.join(name).join(format!("{name}.vars.toml"))
+    }
+
+    /// Returns the last-synced "base" snapshot for `name`, or `None` if the
+    /// skill has never been synced (e.g. its first sync).
+    pub fn load_base(&self, name: &str) -> Result<Option<String>> {
+        let path = self.base_path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read base snapshot '{}'", path.display()))?;
+        Ok(Some(content))
+    }
+
+    /// Records `content` as the new base snapshot for `name`, so the next
+    /// sync has a fresh common ancestor to diff against.
+    pub fn save_base(&self, name: &str, content: &str) -> Result<()> {
+        let path = self.base_path(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create skill dir '{}'", parent.display()))?;
+        }
+        fs::write(&path, content)
+            .with_context(|| format!("failed to write base snapshot '{}'", path.display()))?;
+        Ok(())
+    }
+
     pub fn load_skill(&self, name: &str) -> Result<String> {
         let path = self.skill_path(name);
         let content = fs::read_to_string(&path)
@@ -38,7 +81,13 @@ impl SkillsStore {
         Ok(content)
     }
 
+    /// Writes `content` as `name`'s SKILL.md, first journaling the content
+    /// it's about to overwrite (if any) so `prime-agent undo` can restore it.
     pub fn save_skill(&self, name: &str, content: &str) -> Result<()> {
+        if self.skill_exists(name) {
+            let previous = self.load_skill(name)?;
+            self.journal.record(name, Operation::SaveSkill, &previous)?;
+        }
         fs::create_dir_all(&self.root)
             .with_context(|| format!("failed to create skills dir '{}'", self.root.display()))?;
         let path = self.skill_path(name);
@@ -57,6 +106,11 @@ impl SkillsStore {
             fs::remove_file(&path)
                 .with_context(|| format!("failed to delete skill '{}'", path.display()))?;
         }
+        let base_path = self.base_path(name);
+        if base_path.exists() {
+            fs::remove_file(&base_path)
+                .with_context(|| format!("failed to delete base snapshot '{}'", base_path.display()))?;
+        }
         Ok(())
     }
 
@@ -88,4 +142,141 @@ impl SkillsStore {
         names.sort();
         Ok(names)
     }
+
+    /// Ranks known skill names by fuzzy match against `query`, highest score
+    /// first, ties broken alphabetically.
+    #[must_use]
+    pub fn find_skills(&self, query: &str) -> Vec<(String, i32)> {
+        let names = self.list_skill_names().unwrap_or_default();
+        let mut scored: Vec<(String, i32)> = names
+            .into_iter()
+            .filter_map(|name| fuzzy_score(&name, query).map(|score| (name, score)))
+            .collect();
+        scored.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        scored
+    }
+
+    /// Resolves `query` to an exact skill name, or errors out with the top
+    /// few fuzzy-matched suggestions if no skill is named exactly `query`.
+    pub fn resolve_one(&self, query: &str) -> Result<String> {
+        if self.skill_exists(query) {
+            return Ok(query.to_string());
+        }
+        let matches = self.find_skills(query);
+        if matches.is_empty() {
+            bail!("no skill named '{query}' found");
+        }
+        let suggestions: Vec<&str> = matches.iter().take(5).map(|(name, _)| name.as_str()).collect();
+        bail!(
+            "no skill named '{query}' found; did you mean: {}?",
+            suggestions.join(", ")
+        );
+    }
+}
+
+/// Subsequence fuzzy matcher, similar in spirit to editor file-finders: a
+/// cheap char-bag rejects candidates missing a query char outright, then a
+/// greedy left-to-right pass matches query chars against the candidate,
+/// scoring consecutive runs and word-boundary (start, or after `-`/`_`)
+/// matches higher. The result is normalized by candidate length so short
+/// tight matches outrank long loose ones.
+fn fuzzy_score(candidate: &str, query: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate_lower = candidate.to_lowercase();
+    let query_lower = query.to_lowercase();
+
+    let bag: std::collections::HashSet<char> = candidate_lower.chars().collect();
+    if !query_lower.chars().all(|ch| bag.contains(&ch)) {
+        return None;
+    }
+
+    let candidate_chars: Vec<char> = candidate_lower.chars().collect();
+    let query_chars: Vec<char> = query_lower.chars().collect();
+
+    let mut score = 0i32;
+    let mut search_from = 0usize;
+    let mut last_matched_idx: Option<usize> = None;
+    for &query_char in &query_chars {
+        let idx = (search_from..candidate_chars.len())
+            .find(|&i| candidate_chars[i] == query_char)?;
+
+        score += 10;
+        if last_matched_idx == Some(idx.wrapping_sub(1)) {
+            score += 5;
+        }
+        if idx == 0 || matches!(candidate_chars[idx - 1], '-' | '_') {
+            score += 8;
+        }
+
+        last_matched_idx = Some(idx);
+        search_from = idx + 1;
+    }
+
+    Some(score * 100 / i32::try_from(candidate_chars.len()).unwrap_or(i32::MAX).max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::JournalStore;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn unique_temp_dir() -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("prime-agent-skills-store-test-{}-{id}", std::process::id()))
+    }
+
+    fn store_with_skills(names: &[&str]) -> (PathBuf, SkillsStore) {
+        let root = unique_temp_dir();
+        let journal = JournalStore::new(root.join(".journal.jsonl"), 500);
+        let store = SkillsStore::new(root.clone(), journal);
+        for name in names {
+            store.save_skill(name, "content").unwrap();
+        }
+        (root, store)
+    }
+
+    #[test]
+    fn fuzzy_score_rejects_candidates_missing_a_query_char() {
+        assert_eq!(fuzzy_score("rust-testing", "xyz"), None);
+    }
+
+    #[test]
+    fn fuzzy_score_ranks_prefix_match_above_scattered_match() {
+        let prefix = fuzzy_score("rust-testing", "rust").unwrap();
+        let scattered = fuzzy_score("rust-testing", "rtn").unwrap();
+        assert!(prefix > scattered, "prefix={prefix} scattered={scattered}");
+    }
+
+    #[test]
+    fn fuzzy_score_empty_query_matches_everything_with_zero_score() {
+        assert_eq!(fuzzy_score("anything", ""), Some(0));
+    }
+
+    #[test]
+    fn resolve_one_finds_exact_match_without_scoring() {
+        let (root, store) = store_with_skills(&["rust-testing", "git-basics"]);
+        assert_eq!(store.resolve_one("rust-testing").unwrap(), "rust-testing");
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn resolve_one_suggests_fuzzy_matches_when_no_exact_name() {
+        let (root, store) = store_with_skills(&["rust-testing", "git-basics"]);
+        let err = store.resolve_one("rust-test").unwrap_err();
+        assert!(err.to_string().contains("rust-testing"));
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn resolve_one_errors_with_no_suggestions_when_nothing_matches() {
+        let (root, store) = store_with_skills(&["rust-testing"]);
+        let err = store.resolve_one("zzz").unwrap_err();
+        assert!(err.to_string().contains("no skill named 'zzz' found"));
+        fs::remove_dir_all(root).ok();
+    }
 }
@@ -1,13 +1,64 @@
 use crate::agents_md::{AgentSection, AgentsDoc};
+use crate::journal::Operation;
+use crate::skill_vars;
 use crate::skills_store::SkillsStore;
-use anyhow::{bail, Context, Result};
-use similar::{ChangeTag, TextDiff};
-use std::collections::BTreeSet;
+use anyhow::{anyhow, bail, Context, Result};
+use similar::{ChangeTag, DiffOp, DiffTag, TextDiff};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::io::{self, Write};
+use std::ops::Range;
 use std::path::Path;
 
-pub fn run_sync(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
+/// How to resolve a hunk where both the skill and AGENTS.md changed the same
+/// region relative to their common base.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Prompt on stdin for each conflicting hunk (the default).
+    Interactive,
+    /// Silently keep the skill's side of every conflict.
+    PreferSkill,
+    /// Silently keep the AGENTS.md side of every conflict.
+    PreferAgents,
+    /// Keep both sides of every conflict: skill lines, then agents lines.
+    Union,
+    /// Abort the sync instead of resolving; the caller reports which skills
+    /// had unresolved conflicts.
+    FailOnConflict,
+}
+
+enum MergeOutcome {
+    Resolved(String),
+    Conflict,
+}
+
+struct PlannedChange {
+    name: String,
+    skill_content: Option<String>,
+    section: Option<AgentSection>,
+}
+
+/// What to do about one skill name after comparing its skill-store and
+/// AGENTS.md sides.
+enum NamePlan {
+    /// Neither side has this name; nothing to do.
+    Absent,
+    /// Both sides already agree; its base snapshot should still be
+    /// refreshed so a later real edit has an ancestor to diff against.
+    Unchanged,
+    /// Something needs writing.
+    Change(PlannedChange),
+    /// Both sides changed the same region and `strategy` left it unresolved.
+    Conflict,
+}
+
+pub fn run_sync(
+    skills_store: &SkillsStore,
+    agents_path: &Path,
+    strategy: MergeStrategy,
+    dry_run: bool,
+    overrides: &HashMap<String, String>,
+) -> Result<()> {
     let (mut agents_doc, original_agents) = read_agents_doc(agents_path)?;
     let mut all_names = BTreeSet::new();
 
@@ -18,37 +69,76 @@ pub fn run_sync(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
         all_names.insert(name);
     }
 
-    let mut updated = false;
+    let mut plans = Vec::new();
+    let mut conflicts = Vec::new();
+    // Every skill that ends up present on both sides after this sync, so its
+    // base snapshot can be refreshed even when nothing actually differed —
+    // otherwise the first sync after a no-op `get` leaves no ancestor for
+    // the next real edit's three-way merge to diff against.
+    let mut synced_names = Vec::new();
     for name in all_names {
         SkillsStore::validate_name(&name)?;
-        let skill_exists = skills_store.skill_exists(&name);
-        let section = agents_doc.get_section(&name).cloned();
-
-        match (skill_exists, section) {
-            (false, Some(section)) => {
-                skills_store.save_skill(&name, &section.content_string())?;
+        match plan_for_name(skills_store, &agents_doc, &name, strategy, overrides)? {
+            NamePlan::Absent => {}
+            NamePlan::Unchanged => synced_names.push(name),
+            NamePlan::Change(change) => {
+                synced_names.push(name);
+                plans.push(change);
             }
-            (true, None) => {
-                let content = skills_store.load_skill(&name)?;
-                agents_doc.upsert_section(AgentSection::from_content(name, &content));
-                updated = true;
+            NamePlan::Conflict => conflicts.push(name),
+        }
+    }
+
+    if !conflicts.is_empty() {
+        return Err(anyhow!(
+            "sync has unresolved conflicts in: {}",
+            conflicts.join(", ")
+        ));
+    }
+
+    if dry_run {
+        if plans.is_empty() {
+            println!("No skills would change.");
+        } else {
+            println!("Skills that would change:");
+            for plan in &plans {
+                println!("  {}", plan.name);
             }
-            (true, Some(section)) => {
-                let skill_content = skills_store.load_skill(&name)?;
-                let agents_content = section.content_string();
-                if normalize_content(&skill_content) != normalize_content(&agents_content) {
-                    let resolved = resolve_conflicts_interactive(&name, &skill_content, &agents_content)?;
-                    skills_store.save_skill(&name, &resolved)?;
-                    agents_doc.upsert_section(AgentSection::from_content(name, &resolved));
-                    updated = true;
-                }
+        }
+        let mut preview_doc = AgentsDoc::parse(&original_agents.clone().unwrap_or_default())
+            .unwrap_or_else(|_| AgentsDoc::empty());
+        for plan in &plans {
+            if let Some(section) = &plan.section {
+                preview_doc.upsert_section(section.clone());
             }
-            (false, None) => {}
         }
+        println!("\n--- AGENTS.md preview ---");
+        println!("{}", preview_doc.render());
+        return Ok(());
+    }
+
+    let mut updated = false;
+    for plan in &plans {
+        if let Some(content) = &plan.skill_content {
+            skills_store.save_skill(&plan.name, content)?;
+        }
+        if let Some(section) = &plan.section {
+            agents_doc.upsert_section(section.clone());
+            updated = true;
+        }
+    }
+    for name in &synced_names {
+        let synced_content = skills_store.load_skill(name)?;
+        skills_store.save_base(name, &synced_content)?;
     }
 
     let rendered = agents_doc.render();
     if updated || original_agents.as_deref() != Some(rendered.as_str()) {
+        if let Some(original) = &original_agents {
+            skills_store
+                .journal()
+                .record(AGENTS_JOURNAL_NAME, Operation::SyncAgents, original)?;
+        }
         fs::write(agents_path, rendered)
             .with_context(|| format!("failed to write '{}'", agents_path.display()))?;
     }
@@ -56,6 +146,169 @@ pub fn run_sync(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Decides what `name` needs, comparing its skill-store and AGENTS.md sides.
+fn plan_for_name(
+    skills_store: &SkillsStore,
+    agents_doc: &AgentsDoc,
+    name: &str,
+    strategy: MergeStrategy,
+    overrides: &HashMap<String, String>,
+) -> Result<NamePlan> {
+    let skill_exists = skills_store.skill_exists(name);
+    let section = agents_doc.get_section(name).cloned();
+
+    match (skill_exists, section) {
+        (false, Some(section)) => Ok(NamePlan::Change(PlannedChange {
+            name: name.to_string(),
+            skill_content: Some(section.content_string()),
+            section: None,
+        })),
+        (true, None) => {
+            let content = skills_store.load_skill(name)?;
+            let rendered = resolve_for_agents(skills_store, name, &content, overrides)?;
+            Ok(NamePlan::Change(PlannedChange {
+                section: Some(AgentSection::from_content(name.to_string(), &rendered)),
+                name: name.to_string(),
+                skill_content: None,
+            }))
+        }
+        (true, Some(section)) => plan_existing(skills_store, name, &section, strategy, overrides),
+        (false, None) => Ok(NamePlan::Absent),
+    }
+}
+
+/// `plan_for_name`'s case where `name` exists on both sides: diffs the
+/// skill's rendered content against `section` relative to the last-synced
+/// base, three-way-merging any genuine divergence.
+fn plan_existing(
+    skills_store: &SkillsStore,
+    name: &str,
+    section: &AgentSection,
+    strategy: MergeStrategy,
+    overrides: &HashMap<String, String>,
+) -> Result<NamePlan> {
+    let skill_raw = skills_store.load_skill(name)?;
+    let skill_rendered = resolve_for_agents(skills_store, name, &skill_raw, overrides)?;
+    // A bare `contains("{{")` misclassifies a skill with a literal, never-closed
+    // `{{` (docs showing template syntax, an escaped-brace sample) as templated
+    // forever, since no real token is ever substituted for it. Base this on
+    // whether resolution actually changed anything instead, so only skills with
+    // at least one well-formed `{{name}}` token that resolved successfully are
+    // treated as templated.
+    let is_templated = skill_rendered != skill_raw;
+    let skill_section = AgentSection::from_content(name.to_string(), &skill_rendered);
+    let skill_body = skill_section.content_lines.join("\n");
+    let agents_body = section.content_lines.join("\n");
+    let body_changed = normalize_content(&skill_body) != normalize_content(&agents_body);
+    let metadata_changed = skill_section.metadata != section.metadata;
+
+    if !body_changed && !metadata_changed {
+        return Ok(NamePlan::Unchanged);
+    }
+
+    let base_raw = skills_store.load_base(name)?;
+    let base_rendered = base_raw
+        .as_deref()
+        .map(|base| resolve_for_agents(skills_store, name, base, overrides))
+        .transpose()?;
+    let base_section = base_rendered.as_deref().map(|base| AgentSection::from_content(name.to_string(), base));
+    let base_body = base_section.as_ref().map(|base| base.content_lines.join("\n"));
+
+    let merged_body = if body_changed {
+        match three_way_merge(name, base_body.as_deref(), &skill_body, &agents_body, strategy)? {
+            MergeOutcome::Resolved(resolved) => resolved,
+            MergeOutcome::Conflict => return Ok(NamePlan::Conflict),
+        }
+    } else {
+        agents_body
+    };
+
+    let merged_metadata = if metadata_changed {
+        match merge_metadata(
+            name,
+            base_section.as_ref().map(|base| &base.metadata),
+            &skill_section.metadata,
+            &section.metadata,
+            strategy,
+        )? {
+            MetadataOutcome::Resolved(merged) => merged,
+            MetadataOutcome::Conflict => return Ok(NamePlan::Conflict),
+        }
+    } else {
+        section.metadata.clone()
+    };
+
+    let merged_section = AgentSection {
+        name: name.to_string(),
+        content_lines: split_lines(&merged_body).into_iter().map(str::to_string).collect(),
+        metadata: merged_metadata,
+    };
+    // A templated skill's SKILL.md is the canonical, unresolved source:
+    // never overwrite it with the rendered text used to reconcile against
+    // AGENTS.md, or the template is destroyed and every future sync sees
+    // the skill as permanently different from AGENTS.md.
+    let skill_content = if is_templated { None } else { Some(merged_section.content_string()) };
+    Ok(NamePlan::Change(PlannedChange {
+        section: Some(merged_section),
+        skill_content,
+        name: name.to_string(),
+    }))
+}
+
+/// Skill-name sentinel journal entries use for AGENTS.md-level pre-images,
+/// since those aren't tied to any single skill.
+const AGENTS_JOURNAL_NAME: &str = "AGENTS.md";
+
+/// Restores the most recently journaled pre-image for `skill` (or, with
+/// `skill` unset, every skill with a journaled change), then re-renders
+/// AGENTS.md from the restored skill content. Returns the names restored.
+pub fn run_undo(
+    skills_store: &SkillsStore,
+    agents_path: &Path,
+    skill: Option<&str>,
+    overrides: &HashMap<String, String>,
+) -> Result<Vec<String>> {
+    let targets = match skill {
+        Some(name) => vec![name.to_string()],
+        None => skills_store.journal().known_skills()?,
+    };
+
+    let mut restored = Vec::new();
+    for name in targets {
+        let Some(entry) = skills_store.journal().latest_pre_image(&name)? else {
+            continue;
+        };
+        skills_store.save_skill(&name, &entry.pre_image)?;
+        restored.push(name);
+    }
+
+    if !restored.is_empty() {
+        let (mut agents_doc, _) = read_agents_doc(agents_path)?;
+        for name in &restored {
+            let content = skills_store.load_skill(name)?;
+            let rendered = resolve_for_agents(skills_store, name, &content, overrides)?;
+            agents_doc.upsert_section(AgentSection::from_content(name.clone(), &rendered));
+        }
+        fs::write(agents_path, agents_doc.render())
+            .with_context(|| format!("failed to write '{}'", agents_path.display()))?;
+    }
+
+    Ok(restored)
+}
+
+/// Renders `raw_content` the same way `get` would before it's compared
+/// against or written into AGENTS.md, resolving `name`'s `{{placeholder}}`
+/// tokens through its vars sidecar. Content with no tokens round-trips
+/// unchanged.
+fn resolve_for_agents(
+    skills_store: &SkillsStore,
+    name: &str,
+    raw_content: &str,
+    overrides: &HashMap<String, String>,
+) -> Result<String> {
+    skill_vars::resolve_template(raw_content, &skills_store.vars_path(name), overrides)
+}
+
 fn read_agents_doc(path: &Path) -> Result<(AgentsDoc, Option<String>)> {
     if path.exists() {
         let contents = fs::read_to_string(path)
@@ -67,6 +320,231 @@ fn read_agents_doc(path: &Path) -> Result<(AgentsDoc, Option<String>)> {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Side {
+    Skill,
+    Agents,
+}
+
+/// Three-way merge of `skill_content` and `agents_content` against their
+/// common `base` (the last-synced snapshot). Regions only one side touched
+/// are applied automatically; regions both sides touched differently are
+/// real conflicts, resolved per `strategy`.
+///
+/// When there is no recorded base yet (first sync of this skill), there is
+/// no ancestor to diff against, so the whole file is treated as one
+/// conflicting region.
+fn three_way_merge(
+    name: &str,
+    base: Option<&str>,
+    skill_content: &str,
+    agents_content: &str,
+    strategy: MergeStrategy,
+) -> Result<MergeOutcome> {
+    let Some(base_content) = base else {
+        return resolve_conflict(name, skill_content, agents_content, strategy);
+    };
+
+    let base_lines: Vec<&str> = split_lines(base_content);
+    let skill_lines: Vec<&str> = split_lines(skill_content);
+    let agents_lines: Vec<&str> = split_lines(agents_content);
+
+    let diff_skill = TextDiff::from_slices(&base_lines, &skill_lines);
+    let diff_agents = TextDiff::from_slices(&base_lines, &agents_lines);
+
+    let mut intervals: Vec<(Range<usize>, Side)> = Vec::new();
+    for op in diff_skill.ops() {
+        if op.tag() != DiffTag::Equal {
+            intervals.push((op.old_range(), Side::Skill));
+        }
+    }
+    for op in diff_agents.ops() {
+        if op.tag() != DiffTag::Equal {
+            intervals.push((op.old_range(), Side::Agents));
+        }
+    }
+    intervals.sort_by_key(|(range, _)| range.start);
+
+    let mut merged: Vec<(Range<usize>, bool, bool)> = Vec::new();
+    for (range, side) in intervals {
+        if let Some(last) = merged.last_mut()
+            && range.start <= last.0.end
+        {
+            last.0.end = last.0.end.max(range.end);
+            match side {
+                Side::Skill => last.1 = true,
+                Side::Agents => last.2 = true,
+            }
+            continue;
+        }
+        let (has_skill, has_agents) = match side {
+            Side::Skill => (true, false),
+            Side::Agents => (false, true),
+        };
+        merged.push((range, has_skill, has_agents));
+    }
+
+    let mut output: Vec<String> = Vec::new();
+    let mut pos = 0usize;
+    for (range, has_skill, has_agents) in merged {
+        if range.start > pos {
+            output.extend(base_lines[pos..range.start].iter().map(|line| (*line).to_string()));
+        }
+        if has_skill && has_agents {
+            let skill_sub = side_content_for_range(&base_lines, diff_skill.ops(), &skill_lines, range.clone());
+            let agents_sub = side_content_for_range(&base_lines, diff_agents.ops(), &agents_lines, range.clone());
+            match resolve_conflict(name, &skill_sub.join("\n"), &agents_sub.join("\n"), strategy)? {
+                MergeOutcome::Resolved(resolved) => {
+                    output.extend(split_lines(&resolved).into_iter().map(str::to_string));
+                }
+                MergeOutcome::Conflict => return Ok(MergeOutcome::Conflict),
+            }
+        } else if has_skill {
+            output.extend(side_content_for_range(&base_lines, diff_skill.ops(), &skill_lines, range.clone()));
+        } else {
+            output.extend(side_content_for_range(&base_lines, diff_agents.ops(), &agents_lines, range.clone()));
+        }
+        pos = range.end;
+    }
+    if pos < base_lines.len() {
+        output.extend(base_lines[pos..].iter().map(|line| (*line).to_string()));
+    }
+
+    Ok(MergeOutcome::Resolved(output.join("\n")))
+}
+
+enum MetadataOutcome {
+    Resolved(BTreeMap<String, String>),
+    Conflict,
+}
+
+/// Merges frontmatter metadata key-by-key: a key only one side changed
+/// relative to `base` takes that side's value; a key both sides changed to
+/// different values is a conflict, resolved per `strategy` like any other
+/// conflicting hunk. With no recorded base, any key that differs between
+/// the two sides is treated as a conflict.
+fn merge_metadata(
+    name: &str,
+    base: Option<&BTreeMap<String, String>>,
+    skill: &BTreeMap<String, String>,
+    agents: &BTreeMap<String, String>,
+    strategy: MergeStrategy,
+) -> Result<MetadataOutcome> {
+    let mut keys: BTreeSet<&String> = skill.keys().chain(agents.keys()).collect();
+    if let Some(base) = base {
+        keys.extend(base.keys());
+    }
+
+    let mut merged = BTreeMap::new();
+    for key in keys {
+        let skill_value = skill.get(key);
+        let agents_value = agents.get(key);
+        if skill_value == agents_value {
+            if let Some(value) = skill_value {
+                merged.insert(key.clone(), value.clone());
+            }
+            continue;
+        }
+
+        let base_value = base.and_then(|base| base.get(key));
+        if skill_value == base_value {
+            if let Some(value) = agents_value {
+                merged.insert(key.clone(), value.clone());
+            }
+        } else if agents_value == base_value {
+            if let Some(value) = skill_value {
+                merged.insert(key.clone(), value.clone());
+            }
+        } else {
+            let display_name = format!("{name} [{key}]");
+            match resolve_conflict(
+                &display_name,
+                skill_value.map_or("", String::as_str),
+                agents_value.map_or("", String::as_str),
+                strategy,
+            )? {
+                MergeOutcome::Resolved(resolved) if resolved.is_empty() => {}
+                MergeOutcome::Resolved(resolved) => {
+                    merged.insert(key.clone(), resolved);
+                }
+                MergeOutcome::Conflict => return Ok(MetadataOutcome::Conflict),
+            }
+        }
+    }
+
+    Ok(MetadataOutcome::Resolved(merged))
+}
+
+/// Resolves a single conflicting region (or, with no base, the whole file)
+/// per the chosen `strategy`.
+fn resolve_conflict(
+    name: &str,
+    skill_side: &str,
+    agents_side: &str,
+    strategy: MergeStrategy,
+) -> Result<MergeOutcome> {
+    match strategy {
+        MergeStrategy::PreferSkill => Ok(MergeOutcome::Resolved(skill_side.to_string())),
+        MergeStrategy::PreferAgents => Ok(MergeOutcome::Resolved(agents_side.to_string())),
+        MergeStrategy::Union => {
+            let mut resolved = skill_side.to_string();
+            if !resolved.is_empty() && !agents_side.is_empty() {
+                resolved.push('\n');
+            }
+            resolved.push_str(agents_side);
+            Ok(MergeOutcome::Resolved(resolved))
+        }
+        MergeStrategy::FailOnConflict => Ok(MergeOutcome::Conflict),
+        MergeStrategy::Interactive => {
+            resolve_conflicts_interactive(name, skill_side, agents_side).map(MergeOutcome::Resolved)
+        }
+    }
+}
+
+/// Reconstructs one side's content for a `base`-relative line range, using
+/// that side's base-diff ops: unchanged sub-ranges come straight from
+/// `base_lines` (they're identical by definition), changed ops contribute
+/// their full replacement lines.
+fn side_content_for_range(
+    base_lines: &[&str],
+    ops: &[DiffOp],
+    side_lines: &[&str],
+    range: Range<usize>,
+) -> Vec<String> {
+    let mut out = Vec::new();
+    for op in ops {
+        let old_range = op.old_range();
+        // A pure insertion reports a zero-width `old_range` sitting at the
+        // insertion point, e.g. `2..2`; the ordinary `end <= start || start
+        // >= end` overlap test always excludes those against a range that
+        // starts or ends at that same point, silently dropping the
+        // insertion. Treat a zero-width op as overlapping whenever its
+        // position falls anywhere within `[range.start, range.end]`
+        // (inclusive of both ends) instead.
+        let overlaps = if old_range.is_empty() {
+            old_range.start >= range.start && old_range.start <= range.end
+        } else {
+            old_range.start < range.end && old_range.end > range.start
+        };
+        if !overlaps {
+            continue;
+        }
+        if op.tag() == DiffTag::Equal {
+            let clip_start = old_range.start.max(range.start);
+            let clip_end = old_range.end.min(range.end);
+            out.extend(base_lines[clip_start..clip_end].iter().map(|line| (*line).to_string()));
+        } else {
+            let new_range = op.new_range();
+            out.extend(side_lines[new_range.start..new_range.end].iter().map(|line| (*line).to_string()));
+        }
+    }
+    out
+}
+
+fn split_lines(content: &str) -> Vec<&str> {
+    content.split('\n').collect()
+}
+
 fn resolve_conflicts_interactive(
     name: &str,
     skill_content: &str,
@@ -148,3 +626,156 @@ fn prompt_choice() -> Result<Choice> {
 fn normalize_content(content: &str) -> String {
     content.replace("\r\n", "\n").trim_end_matches('\n').to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn three_way(
+        base: Option<&str>,
+        skill: &str,
+        agents: &str,
+        strategy: MergeStrategy,
+    ) -> Result<MergeOutcome> {
+        three_way_merge("demo", base, skill, agents, strategy)
+    }
+
+    fn resolved(outcome: MergeOutcome) -> String {
+        match outcome {
+            MergeOutcome::Resolved(text) => text,
+            MergeOutcome::Conflict => panic!("expected a resolved merge, got a conflict"),
+        }
+    }
+
+    #[test]
+    fn replace_only_applies_the_changed_side() {
+        let base = "line1\nline2\nline3";
+        let skill = "line1\nline2-edited\nline3";
+        let merged = resolved(three_way(Some(base), skill, base, MergeStrategy::FailOnConflict).unwrap());
+        assert_eq!(merged, skill);
+    }
+
+    #[test]
+    fn insert_only_is_not_silently_dropped() {
+        // Regression test: `side_content_for_range`'s overlap check used to
+        // exclude zero-width insertion ops, so a skill-side append with no
+        // agents-side change rendered as "no change" and the inserted line
+        // never made it into the merge output.
+        let base = "line1\nline2";
+        let skill = "line1\nline2\nline3-new";
+        let merged = resolved(three_way(Some(base), skill, base, MergeStrategy::FailOnConflict).unwrap());
+        assert_eq!(merged, skill);
+    }
+
+    #[test]
+    fn insert_only_with_unrelated_agents_change_merges_both() {
+        let base = "line1\nline2\nline3";
+        let skill = "line1\nline2\nline3\nline4-new";
+        let agents = "line1-edited\nline2\nline3";
+        let merged = resolved(three_way(Some(base), skill, agents, MergeStrategy::FailOnConflict).unwrap());
+        assert_eq!(merged, "line1-edited\nline2\nline3\nline4-new");
+    }
+
+    #[test]
+    fn delete_only_applies_the_changed_side() {
+        let base = "line1\nline2\nline3";
+        let skill = "line1\nline3";
+        let merged = resolved(three_way(Some(base), skill, base, MergeStrategy::FailOnConflict).unwrap());
+        assert_eq!(merged, skill);
+    }
+
+    #[test]
+    fn mixed_conflicting_region_fails_on_conflict() {
+        let base = "line1\nline2\nline3";
+        let skill = "line1\nline2-from-skill\nline3";
+        let agents = "line1\nline2-from-agents\nline3";
+        let outcome = three_way(Some(base), skill, agents, MergeStrategy::FailOnConflict).unwrap();
+        assert!(matches!(outcome, MergeOutcome::Conflict));
+    }
+
+    #[test]
+    fn mixed_conflicting_region_prefer_skill() {
+        let base = "line1\nline2\nline3";
+        let skill = "line1\nline2-from-skill\nline3";
+        let agents = "line1\nline2-from-agents\nline3";
+        let merged = resolved(three_way(Some(base), skill, agents, MergeStrategy::PreferSkill).unwrap());
+        assert_eq!(merged, skill);
+    }
+
+    #[test]
+    fn mixed_conflicting_region_prefer_agents() {
+        let base = "line1\nline2\nline3";
+        let skill = "line1\nline2-from-skill\nline3";
+        let agents = "line1\nline2-from-agents\nline3";
+        let merged = resolved(three_way(Some(base), skill, agents, MergeStrategy::PreferAgents).unwrap());
+        assert_eq!(merged, agents);
+    }
+
+    #[test]
+    fn mixed_conflicting_region_union_keeps_both_sides() {
+        let base = "line1\nline2\nline3";
+        let skill = "line1\nline2-from-skill\nline3";
+        let agents = "line1\nline2-from-agents\nline3";
+        let merged = resolved(three_way(Some(base), skill, agents, MergeStrategy::Union).unwrap());
+        assert_eq!(merged, "line1\nline2-from-skill\nline2-from-agents\nline3");
+    }
+
+    fn unique_temp_dir(label: &str) -> std::path::PathBuf {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("prime-agent-sync-test-{label}-{}-{id}", std::process::id()))
+    }
+
+    fn store_at(root: std::path::PathBuf) -> SkillsStore {
+        let journal = crate::journal::JournalStore::new(root.join(".journal.jsonl"), 500);
+        SkillsStore::new(root, journal)
+    }
+
+    /// Drives `run_sync` end-to-end against a real `SkillsStore`/AGENTS.md on
+    /// disk for a pure skill-side insertion (no agents-side change, so every
+    /// strategy takes the same non-conflicting path), confirming `--dry-run`
+    /// previews without writing and a real sync applies the insertion rather
+    /// than dropping it.
+    fn run_sync_insertion_case(strategy: MergeStrategy) {
+        let root = unique_temp_dir("insertion");
+        let skills_dir = root.join("skills");
+        let agents_path = root.join("AGENTS.md");
+        let skills_store = store_at(skills_dir.clone());
+        let overrides = HashMap::new();
+
+        skills_store.save_skill("demo", "line1\nline2").unwrap();
+        run_sync(&skills_store, &agents_path, strategy, false, &overrides).unwrap();
+
+        skills_store.save_skill("demo", "line1\nline2\nline3-new").unwrap();
+
+        let before_dry_run = fs::read_to_string(&agents_path).unwrap();
+        run_sync(&skills_store, &agents_path, strategy, true, &overrides).unwrap();
+        assert_eq!(
+            fs::read_to_string(&agents_path).unwrap(),
+            before_dry_run,
+            "--dry-run must not write AGENTS.md"
+        );
+
+        run_sync(&skills_store, &agents_path, strategy, false, &overrides).unwrap();
+        let rendered = fs::read_to_string(&agents_path).unwrap();
+        assert!(
+            rendered.contains("line3-new"),
+            "sync with strategy {strategy:?} dropped an insert-only change:\n{rendered}"
+        );
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn run_sync_preserves_insertions_for_every_strategy() {
+        for strategy in [
+            MergeStrategy::PreferSkill,
+            MergeStrategy::PreferAgents,
+            MergeStrategy::Union,
+            MergeStrategy::FailOnConflict,
+        ] {
+            run_sync_insertion_case(strategy);
+        }
+    }
+}